@@ -0,0 +1,9 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+pub mod pipelines;
+pub mod sessions;
+
+#[cfg(test)]
+pub mod tests;
@@ -0,0 +1,46 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_streams::SendableDataBlockStream;
+
+mod processor_mixed;
+
+pub use processor_mixed::MixedDistributionPolicy;
+pub use processor_mixed::MixedProcessor;
+
+#[async_trait::async_trait]
+pub trait Processor: Sync + Send {
+    fn name(&self) -> &str;
+
+    fn connect_to(&mut self, input: Arc<dyn Processor>) -> Result<()>;
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>>;
+
+    fn as_any(&self) -> &dyn Any;
+
+    async fn execute(&self) -> Result<SendableDataBlockStream>;
+
+    /// Whether this processor's stream does CPU-bound work (decompression,
+    /// column materialization, index evaluation, ...) rather than just
+    /// waiting on I/O. Defaults to `false`; CPU-bound processors override
+    /// this so their consumers run `cpu_transform` on each block on the
+    /// dedicated blocking pool. The stream itself is still polled on the
+    /// shared async reactor.
+    fn is_cpu_bound(&self) -> bool {
+        false
+    }
+
+    /// The synchronous, CPU-bound step applied to each block this processor
+    /// produces (decoding, materialization, ...). Only ever called when
+    /// `is_cpu_bound()` is `true`, and always off the async reactor — see
+    /// `MixedWorker::drain_input`. Defaults to the identity transform.
+    fn cpu_transform(&self, block: DataBlock) -> Result<DataBlock> {
+        Ok(block)
+    }
+}
@@ -21,13 +21,62 @@ use tokio_stream::StreamExt;
 use crate::pipelines::processors::Processor;
 use crate::sessions::FuseQueryContextRef;
 
-// M inputs--> N outputs Mixed processor
+// The shared capacity of the work-stealing queue. It is intentionally small: the
+// queue only needs to smooth out bursts, every output still pulls as fast as it can.
+const SHARED_QUEUE_CAPACITY: usize = 64;
+
+/// How `MixedProcessor` hands blocks from its merged input to its N outputs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MixedDistributionPolicy {
+    /// Strict round-robin over per-output channels: block `k` always lands on
+    /// output `k % n` via a blocking awaited send, even if that output is
+    /// congested.
+    RoundRobin,
+    /// Round-robin ordering, but a congested output is skipped in favor of
+    /// one with free capacity via `try_reserve`; falls back to an awaited
+    /// send only once every output is full.
+    RoundRobinCapacityAware,
+    /// All outputs drain a single shared queue, so a slow consumer only slows
+    /// itself down instead of blocking the others.
+    CapacityAware,
+    /// Every output receives its own copy of every block, instead of each
+    /// block going to exactly one output. Used to feed the same intermediate
+    /// result into several independent downstream pipeline branches.
+    ///
+    /// "Per-output backpressure" here means each output's channel fill level
+    /// is tracked independently, not that outputs are paced independently of
+    /// one another: the distributor awaits each send in turn, so a live output
+    /// that is merely slow (not dropped) still stalls the others once its
+    /// channel is full — every consumer must see every block, so there is no
+    /// way to let a fast output skip ahead without dropping data for the slow
+    /// one. The one case that does not stall the rest is a *dropped* output,
+    /// per the broadcast contract: its send errors are logged and skipped.
+    Broadcast,
+}
+
+impl Default for MixedDistributionPolicy {
+    fn default() -> Self {
+        MixedDistributionPolicy::CapacityAware
+    }
+}
+
+// M inputs--> N outputs Mixed processor. How blocks reach the outputs depends on
+// `policy`: with the default `CapacityAware` policy outputs work-steal from a
+// shared queue; the other policies feed each output its own channel.
 struct MixedWorker {
     ctx: FuseQueryContextRef,
     inputs: Vec<Arc<dyn Processor>>,
     n: usize,
+    policy: MixedDistributionPolicy,
     shared_num: AtomicUsize,
     started: AtomicBool,
+    // All N outputs drain from the same MPMC queue, so a slow consumer never blocks
+    // the others: whichever output asks for the next block first gets it. `flume`
+    // gives every side an async recv/send that parks instead of busy-polling when
+    // the queue is empty or full. Only populated when `policy` is `CapacityAware`.
+    shared_receiver: Option<flume::Receiver<Result<DataBlock>>>,
+    // Per-output channels used when `policy` is `RoundRobin`,
+    // `RoundRobinCapacityAware`, or `Broadcast`.
     receivers: Vec<Option<mpsc::Receiver<Result<DataBlock>>>>,
 }
 
@@ -43,52 +92,120 @@ impl MixedWorker {
                 for i in 0..inputs {
                     let input = self.inputs[i].clone();
                     let sender = sender.clone();
-                    self.ctx.execute_task(async move {
-                        let mut stream = match input.execute().await {
-                            Err(e) => {
-                                if let Err(error) = sender.send(Result::Err(e)).await {
-                                    error!("Mixed processor cannot push data: {}", error);
-                                }
-                                return;
-                            }
-                            Ok(stream) => stream,
-                        };
-
-                        while let Some(item) = stream.next().await {
-                            match item {
-                                Ok(item) => {
-                                    if let Err(error) = sender.send(Ok(item)).await {
-                                        // Stop pulling data
-                                        error!("Mixed processor cannot push data: {}", error);
-                                        return;
-                                    }
-                                }
-                                Err(error) => {
-                                    // Stop pulling data
-                                    if let Err(error) = sender.send(Err(error)).await {
-                                        error!("Mixed processor cannot push data: {}", error);
-                                    }
-                                    return;
-                                }
-                            }
-                        }
-                    })?;
+                    let ctx = self.ctx.clone();
+                    self.ctx.execute_task(Self::drain_input(ctx, input, sender))?;
                 }
                 Ok(Box::pin(ReceiverStream::new(receiver)))
             }
         }
     }
 
+    async fn drain_input(
+        ctx: FuseQueryContextRef,
+        input: Arc<dyn Processor>,
+        sender: mpsc::Sender<Result<DataBlock>>,
+    ) {
+        let cpu_bound = input.is_cpu_bound();
+        let mut stream = match input.execute().await {
+            Err(e) => {
+                if let Err(error) = sender.send(Result::Err(e)).await {
+                    error!("Mixed processor cannot push data: {}", error);
+                }
+                return;
+            }
+            Ok(stream) => stream,
+        };
+
+        // The stream itself always steps on the reactor, same as any other
+        // input: whatever I/O it waits on (disk, network) belongs there, not
+        // on the CPU pool. Only the synchronous per-block transform for
+        // CPU-bound inputs is handed to `execute_blocking`, so a pool thread
+        // is never held across an `await`.
+        while let Some(next) = stream.next().await {
+            let item = match next {
+                Ok(block) if cpu_bound => Self::transform_on_cpu_pool(&ctx, &input, block).await,
+                other => other,
+            };
+
+            match item {
+                Ok(item) => {
+                    if let Err(error) = sender.send(Ok(item)).await {
+                        // Stop pulling data
+                        error!("Mixed processor cannot push data: {}", error);
+                        return;
+                    }
+                }
+                Err(error) => {
+                    // Stop pulling data
+                    if let Err(error) = sender.send(Err(error)).await {
+                        error!("Mixed processor cannot push data: {}", error);
+                    }
+                    return;
+                }
+            }
+        }
+    }
+
+    // Runs a CPU-bound input's synchronous per-block transform on the
+    // dedicated worker pool, off the shared async reactor.
+    async fn transform_on_cpu_pool(
+        ctx: &FuseQueryContextRef,
+        input: &Arc<dyn Processor>,
+        block: DataBlock,
+    ) -> Result<DataBlock> {
+        let input = input.clone();
+        ctx.execute_blocking(move || input.cpu_transform(block))
+            .await
+    }
+
     pub fn start(&mut self) -> Result<()> {
         if self.started.load(Ordering::Relaxed) {
             return Ok(());
         }
 
+        match self.policy {
+            MixedDistributionPolicy::CapacityAware => self.start_capacity_aware()?,
+            MixedDistributionPolicy::RoundRobin => self.start_round_robin()?,
+            MixedDistributionPolicy::RoundRobinCapacityAware => {
+                self.start_round_robin_capacity_aware()?
+            }
+            MixedDistributionPolicy::Broadcast => self.start_broadcast()?,
+        }
+
+        self.started.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn start_capacity_aware(&mut self) -> Result<()> {
+        let (shared_sender, shared_receiver) =
+            flume::bounded::<Result<DataBlock>>(SHARED_QUEUE_CAPACITY);
+        self.shared_receiver = Some(shared_receiver);
+
+        let mut stream = self.prepare_inputstream()?;
+        self.ctx.execute_task(async move {
+            // `shared_sender` is dropped with this task once the input stream
+            // ends, which closes the shared queue and lets every consumer
+            // observe end-of-stream via a `recv` error. `send_async` parks
+            // this task (instead of spinning) while the queue is full.
+            while let Some(item) = stream.next().await {
+                if shared_sender.send_async(item).await.is_err() {
+                    error!("Mixed processor cannot push data: channel disconnected");
+                    return;
+                }
+            }
+        })?;
+
+        Ok(())
+    }
+
+    // Genuinely strict: block `k` always lands on output `k % n`, blocking
+    // this task if that output happens to be congested.
+    fn start_round_robin(&mut self) -> Result<()> {
         let inputs = self.inputs.len();
         let outputs = self.n;
 
         let mut senders = Vec::with_capacity(outputs);
-        for _i in 0..self.n {
+        for _i in 0..outputs {
             let (sender, receiver) = mpsc::channel::<Result<DataBlock>>(inputs);
             senders.push(sender);
             self.receivers.push(Some(receiver));
@@ -96,19 +213,131 @@ impl MixedWorker {
 
         let mut stream = self.prepare_inputstream()?;
         self.ctx.execute_task(async move {
-            let index = AtomicUsize::new(0);
+            let mut next = 0usize;
             while let Some(item) = stream.next().await {
-                let i = index.fetch_add(1, Ordering::Relaxed) % outputs;
-                // TODO: USE try_reserve when the channel is blocking
+                let i = next;
+                next = (i + 1) % outputs;
                 if let Err(error) = senders[i].send(item).await {
                     error!("Mixed processor cannot push data: {}", error);
                 }
             }
         })?;
 
-        self.started.store(true, Ordering::Relaxed);
         Ok(())
     }
+
+    // Round-robin ordering, but a congested output is skipped via
+    // `try_reserve` in favor of the next one with free capacity, falling
+    // back to an awaited send only once every output is full.
+    fn start_round_robin_capacity_aware(&mut self) -> Result<()> {
+        let inputs = self.inputs.len();
+        let outputs = self.n;
+
+        let mut senders = Vec::with_capacity(outputs);
+        for _i in 0..outputs {
+            let (sender, receiver) = mpsc::channel::<Result<DataBlock>>(inputs);
+            senders.push(sender);
+            self.receivers.push(Some(receiver));
+        }
+
+        let mut stream = self.prepare_inputstream()?;
+        self.ctx.execute_task(async move {
+            let mut next = 0usize;
+            while let Some(item) = stream.next().await {
+                let mut reserved = None;
+                for offset in 0..outputs {
+                    let i = (next + offset) % outputs;
+                    if let Ok(permit) = senders[i].try_reserve() {
+                        reserved = Some((i, permit));
+                        break;
+                    }
+                }
+
+                match reserved {
+                    Some((i, permit)) => {
+                        permit.send(item);
+                        next = (i + 1) % outputs;
+                    }
+                    // Every output is at capacity: fall back to a blocking send on
+                    // the next output in line rather than picking one arbitrarily.
+                    None => {
+                        let i = next;
+                        next = (i + 1) % outputs;
+                        if let Err(error) = senders[i].send(item).await {
+                            error!("Mixed processor cannot push data: {}", error);
+                        }
+                    }
+                }
+            }
+        })?;
+
+        Ok(())
+    }
+
+    fn start_broadcast(&mut self) -> Result<()> {
+        let outputs = self.n;
+
+        let mut senders = Vec::with_capacity(outputs);
+        for _i in 0..outputs {
+            let (sender, receiver) = mpsc::channel::<Result<DataBlock>>(SHARED_QUEUE_CAPACITY);
+            senders.push(sender);
+            self.receivers.push(Some(receiver));
+        }
+
+        let mut stream = self.prepare_inputstream()?;
+        self.ctx.execute_task(async move {
+            while let Some(item) = stream.next().await {
+                for sender in senders.iter() {
+                    // A dropped output must not stop the rest from receiving data,
+                    // so a failed send here is logged rather than aborting the loop.
+                    if let Err(error) = sender.send(item.clone()).await {
+                        error!("Mixed processor cannot push data: {}", error);
+                    }
+                }
+            }
+        })?;
+
+        Ok(())
+    }
+
+    // Each output pulls its own blocks, either by stealing from the shared queue
+    // (`CapacityAware`) or from its own pre-assigned channel (`RoundRobin` /
+    // `RoundRobinCapacityAware` / `Broadcast`).
+    pub fn consumer_stream(&mut self, index: usize) -> Result<SendableDataBlockStream> {
+        match self.policy {
+            MixedDistributionPolicy::CapacityAware => self.consumer_stream_capacity_aware(),
+            MixedDistributionPolicy::RoundRobin
+            | MixedDistributionPolicy::RoundRobinCapacityAware
+            | MixedDistributionPolicy::Broadcast => {
+                let receiver = self.receivers[index]
+                    .take()
+                    .ok_or_else(|| ErrorCode::LogicalError("Mixed processor output reused"))?;
+                Ok(Box::pin(ReceiverStream::new(receiver)))
+            }
+        }
+    }
+
+    fn consumer_stream_capacity_aware(&self) -> Result<SendableDataBlockStream> {
+        let shared_receiver = self
+            .shared_receiver
+            .clone()
+            .ok_or_else(|| ErrorCode::LogicalError("Mixed processor has not been started"))?;
+
+        let (sender, receiver) = mpsc::channel::<Result<DataBlock>>(1);
+        self.ctx.execute_task(async move {
+            // `recv_async` parks this task (instead of spinning) while the
+            // shared queue is empty, and ends the loop once the distributor
+            // has dropped `shared_sender` and the queue is drained.
+            while let Ok(item) = shared_receiver.recv_async().await {
+                if let Err(error) = sender.send(item).await {
+                    error!("Mixed processor cannot push data: {}", error);
+                    return;
+                }
+            }
+        })?;
+
+        Ok(Box::pin(ReceiverStream::new(receiver)))
+    }
 }
 
 pub struct MixedProcessor {
@@ -118,12 +347,22 @@ pub struct MixedProcessor {
 
 impl MixedProcessor {
     pub fn create(ctx: FuseQueryContextRef, n: usize) -> Self {
+        Self::create_with_policy(ctx, n, MixedDistributionPolicy::default())
+    }
+
+    pub fn create_with_policy(
+        ctx: FuseQueryContextRef,
+        n: usize,
+        policy: MixedDistributionPolicy,
+    ) -> Self {
         let worker = MixedWorker {
             ctx,
             inputs: vec![],
             n,
+            policy,
             started: AtomicBool::new(false),
             shared_num: AtomicUsize::new(0),
+            shared_receiver: None,
             receivers: vec![],
         };
 
@@ -170,13 +409,252 @@ impl Processor for MixedProcessor {
     }
 
     async fn execute(&self) -> Result<SendableDataBlockStream> {
-        let receiver = {
-            let mut worker = self.worker.write();
-            worker.start()?;
-            worker.receivers[self.index].take()
+        let mut worker = self.worker.write();
+        worker.start()?;
+        worker.consumer_stream(self.index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_datablocks::DataBlock;
+
+    use super::*;
+
+    // Feeds a fixed batch of empty blocks, all available immediately.
+    struct OneShotProcessor {
+        blocks: common_infallible::Mutex<Vec<DataBlock>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Processor for OneShotProcessor {
+        fn name(&self) -> &str {
+            "OneShotProcessor"
         }
-        .unwrap();
 
-        Ok(Box::pin(ReceiverStream::new(receiver)))
+        fn connect_to(&mut self, _input: Arc<dyn Processor>) -> Result<()> {
+            Ok(())
+        }
+
+        fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+            vec![]
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        async fn execute(&self) -> Result<SendableDataBlockStream> {
+            let blocks = self.blocks.lock().drain(..).collect::<Vec<_>>();
+            Ok(Box::pin(tokio_stream::iter(blocks.into_iter().map(Ok))))
+        }
+    }
+
+    // Like `OneShotProcessor`, but marks itself CPU-bound and counts how many
+    // blocks were routed through `cpu_transform`, so tests can assert that
+    // path actually ran instead of silently falling back to a no-op.
+    struct CpuBoundProcessor {
+        blocks: common_infallible::Mutex<Vec<DataBlock>>,
+        transformed: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl Processor for CpuBoundProcessor {
+        fn name(&self) -> &str {
+            "CpuBoundProcessor"
+        }
+
+        fn connect_to(&mut self, _input: Arc<dyn Processor>) -> Result<()> {
+            Ok(())
+        }
+
+        fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+            vec![]
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        async fn execute(&self) -> Result<SendableDataBlockStream> {
+            let blocks = self.blocks.lock().drain(..).collect::<Vec<_>>();
+            Ok(Box::pin(tokio_stream::iter(blocks.into_iter().map(Ok))))
+        }
+
+        fn is_cpu_bound(&self) -> bool {
+            true
+        }
+
+        fn cpu_transform(&self, block: DataBlock) -> Result<DataBlock> {
+            self.transformed.fetch_add(1, Ordering::Relaxed);
+            Ok(block)
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_cpu_bound_input_is_transformed_on_the_worker_pool() -> Result<()> {
+        let ctx = crate::tests::try_create_context()?;
+        let block_count = 20;
+
+        let input = Arc::new(CpuBoundProcessor {
+            blocks: common_infallible::Mutex::new(
+                (0..block_count).map(|_| DataBlock::empty()).collect(),
+            ),
+            transformed: AtomicUsize::new(0),
+        });
+
+        let mut mixed = MixedProcessor::create(ctx, 1);
+        mixed.connect_to(input.clone())?;
+
+        let mut stream = mixed.execute().await?;
+        let mut count = 0;
+        while stream.next().await.transpose()?.is_some() {
+            count += 1;
+        }
+
+        assert_eq!(count, block_count);
+        assert_eq!(input.transformed.load(Ordering::Relaxed), block_count);
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_context_execute_blocking_runs_on_the_cpu_pool() -> Result<()> {
+        let ctx = crate::tests::try_create_context()?;
+
+        let doubled = ctx.execute_blocking(|| Ok(21 * 2)).await?;
+
+        assert_eq!(doubled, 42);
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_capacity_aware_slow_consumer_does_not_block_others() -> Result<()> {
+        let ctx = crate::tests::try_create_context()?;
+        let block_count = 20;
+
+        let mut mixed =
+            MixedProcessor::create_with_policy(ctx, 2, MixedDistributionPolicy::CapacityAware);
+        mixed.connect_to(Arc::new(OneShotProcessor {
+            blocks: common_infallible::Mutex::new(
+                (0..block_count).map(|_| DataBlock::empty()).collect(),
+            ),
+        }))?;
+
+        let fast = mixed.share()?;
+
+        // Drain `fast` to completion while never polling `mixed` (the "slow"
+        // output): with capacity-aware distribution the slow output must not
+        // stop `fast` from stealing every block.
+        let mut fast_stream = fast.execute().await?;
+        let mut fast_count = 0;
+        while fast_stream.next().await.transpose()?.is_some() {
+            fast_count += 1;
+        }
+
+        assert_eq!(fast_count, block_count);
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_round_robin_splits_blocks_across_outputs() -> Result<()> {
+        let ctx = crate::tests::try_create_context()?;
+        let block_count = 20;
+
+        let mut mixed =
+            MixedProcessor::create_with_policy(ctx, 2, MixedDistributionPolicy::RoundRobin);
+        mixed.connect_to(Arc::new(OneShotProcessor {
+            blocks: common_infallible::Mutex::new(
+                (0..block_count).map(|_| DataBlock::empty()).collect(),
+            ),
+        }))?;
+
+        let second = mixed.share()?;
+
+        let first_stream = mixed.execute().await?;
+        let second_stream = second.execute().await?;
+
+        // Strict round-robin awaits each send on output `k % n`, so draining the
+        // outputs one after another would stall once the undrained output's
+        // channel fills up; drain them concurrently instead.
+        let drain = |mut stream: SendableDataBlockStream| async move {
+            let mut count = 0;
+            while stream.next().await.transpose()?.is_some() {
+                count += 1;
+            }
+            Result::Ok(count)
+        };
+        let (first_count, second_count) =
+            common_runtime::tokio::join!(drain(first_stream), drain(second_stream));
+
+        assert_eq!(first_count? + second_count?, block_count);
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_round_robin_capacity_aware_bypasses_congested_output() -> Result<()> {
+        let ctx = crate::tests::try_create_context()?;
+        let block_count = 20;
+
+        let mut mixed = MixedProcessor::create_with_policy(
+            ctx,
+            2,
+            MixedDistributionPolicy::RoundRobinCapacityAware,
+        );
+        mixed.connect_to(Arc::new(OneShotProcessor {
+            blocks: common_infallible::Mutex::new(
+                (0..block_count).map(|_| DataBlock::empty()).collect(),
+            ),
+        }))?;
+
+        // `slow` is never drained: its channel buffers exactly one block before
+        // `try_reserve` starts failing, after which the distributor must keep
+        // routing blocks to `fast` instead of blocking on `slow`.
+        let slow = mixed.share()?;
+        let mut fast_stream = mixed.execute().await?;
+
+        let mut fast_count = 0;
+        while fast_stream.next().await.transpose()?.is_some() {
+            fast_count += 1;
+        }
+
+        assert_eq!(fast_count, block_count - 1);
+        drop(slow);
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_broadcast_sends_every_block_to_every_output() -> Result<()> {
+        let ctx = crate::tests::try_create_context()?;
+        let block_count = 20;
+
+        let mut mixed =
+            MixedProcessor::create_with_policy(ctx, 2, MixedDistributionPolicy::Broadcast);
+        mixed.connect_to(Arc::new(OneShotProcessor {
+            blocks: common_infallible::Mutex::new(
+                (0..block_count).map(|_| DataBlock::empty()).collect(),
+            ),
+        }))?;
+
+        let second = mixed.share()?;
+
+        let first_stream = mixed.execute().await?;
+        let second_stream = second.execute().await?;
+
+        // Every output must see every block, and broadcast awaits each send in
+        // turn, so draining the outputs one after another (rather than
+        // concurrently) would stall once the undrained output's channel fills up.
+        let drain = |mut stream: SendableDataBlockStream| async move {
+            let mut count = 0;
+            while stream.next().await.transpose()?.is_some() {
+                count += 1;
+            }
+            Result::Ok(count)
+        };
+        let (first_count, second_count) =
+            common_runtime::tokio::join!(drain(first_stream), drain(second_stream));
+
+        assert_eq!(first_count?, block_count);
+        assert_eq!(second_count?, block_count);
+        Ok(())
     }
 }
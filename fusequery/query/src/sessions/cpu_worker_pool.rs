@@ -0,0 +1,70 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::sync::Arc;
+use std::thread;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_runtime::tokio::sync::oneshot;
+
+/// A fixed pool of OS threads for CPU-bound work (decompression, column
+/// materialization, index evaluation, ...) that must not run on the shared
+/// async reactor, where it would stall I/O-bound tasks sharing the runtime.
+///
+/// Work is handed to the pool over a single `flume` channel; every worker
+/// thread loops on `recv()` and runs whatever closure it is given, returning
+/// the result through a oneshot so the caller can `.await` it like any other
+/// async operation.
+pub struct CpuWorkerPool {
+    sender: flume::Sender<Job>,
+}
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+impl CpuWorkerPool {
+    /// Creates a pool sized from the available cores, unless `size` overrides it.
+    pub fn create(size: Option<usize>) -> Arc<CpuWorkerPool> {
+        let workers = size.unwrap_or_else(num_cpus::get).max(1);
+
+        let (sender, receiver) = flume::unbounded::<Job>();
+        for index in 0..workers {
+            let receiver = receiver.clone();
+            thread::Builder::new()
+                .name(format!("cpu-worker-pool-{}", index))
+                .spawn(move || {
+                    while let Ok(job) = receiver.recv() {
+                        job();
+                    }
+                })
+                .expect("failed to spawn cpu worker pool thread");
+        }
+
+        Arc::new(CpuWorkerPool { sender })
+    }
+
+    /// Runs `f` on the pool and resolves once it finishes. `f` is free to block
+    /// the worker thread; the returned future only blocks the task awaiting it.
+    pub async fn execute_blocking<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> Result<T> + Send + 'static,
+    {
+        let (result_sender, result_receiver) = oneshot::channel::<Result<T>>();
+
+        let job: Job = Box::new(move || {
+            // The receiving side may have been dropped if the caller was
+            // cancelled; there is nothing useful to do with that send error.
+            let _ = result_sender.send(f());
+        });
+
+        self.sender
+            .send(job)
+            .map_err(|_| ErrorCode::LogicalError("CPU worker pool has been shut down"))?;
+
+        result_receiver
+            .await
+            .map_err(|_| ErrorCode::LogicalError("CPU worker pool dropped the job result"))?
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::future::Future;
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_runtime::tokio::runtime::Handle;
+
+mod cpu_worker_pool;
+
+pub use cpu_worker_pool::CpuWorkerPool;
+
+pub type FuseQueryContextRef = Arc<FuseQueryContext>;
+
+pub struct FuseQueryContext {
+    runtime: Handle,
+    cpu_pool: Arc<CpuWorkerPool>,
+}
+
+impl FuseQueryContext {
+    pub fn try_create() -> Result<FuseQueryContextRef> {
+        Self::try_create_with_cpu_pool_size(None)
+    }
+
+    /// `cpu_pool_size` overrides the default of one CPU worker per available
+    /// core.
+    pub fn try_create_with_cpu_pool_size(
+        cpu_pool_size: Option<usize>,
+    ) -> Result<FuseQueryContextRef> {
+        Ok(Arc::new(FuseQueryContext {
+            runtime: Handle::current(),
+            cpu_pool: CpuWorkerPool::create(cpu_pool_size),
+        }))
+    }
+
+    /// Spawns `task` on the shared async runtime. Use `execute_blocking` for
+    /// synchronous CPU-bound work instead.
+    pub fn execute_task<T>(&self, task: T) -> Result<()>
+    where T: Future<Output = ()> + Send + 'static {
+        self.runtime.spawn(task);
+        Ok(())
+    }
+
+    /// Runs `f` on the dedicated CPU worker pool instead of the shared async
+    /// runtime, so CPU-bound work (decompression, column materialization,
+    /// index evaluation, ...) can't stall the reactor.
+    pub async fn execute_blocking<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> Result<T> + Send + 'static,
+    {
+        self.cpu_pool.execute_blocking(f).await
+    }
+}
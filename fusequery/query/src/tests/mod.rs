@@ -0,0 +1,12 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_exception::Result;
+
+use crate::sessions::FuseQueryContext;
+use crate::sessions::FuseQueryContextRef;
+
+pub fn try_create_context() -> Result<FuseQueryContextRef> {
+    FuseQueryContext::try_create()
+}